@@ -2,19 +2,27 @@
 #![allow(clippy::redundant_field_names)]
 
 use std::{
+	collections::VecDeque,
 	error::Error,
-	fs::File,
+	fs::{self, File},
 	io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
-	path::PathBuf,
+	path::{Path, PathBuf},
 };
 
 use clap::{Arg, ArgAction, Command};
 use log::{debug, error, info, log, Level, LevelFilter};
+use rayon::prelude::*;
 use xxhash_rust::xxh3::Xxh3;
 
-use arrow2::io::{
-	ipc::write::Compression,
-	json::write as json_write,
+use arrow2::{
+	array::Array,
+	chunk::Chunk,
+	datatypes::{Field, Schema},
+	io::{
+		ipc::write::Compression,
+		json::write as json_write,
+		parquet::write as parquet_write,
+	},
 };
 
 use peppi::{
@@ -28,7 +36,7 @@ use peppi::{
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Format {
-	Json, Peppi, Slippi, Null
+	Json, Parquet, Peppi, Slippi, Null
 }
 
 impl TryFrom<&str> for Format {
@@ -36,6 +44,7 @@ impl TryFrom<&str> for Format {
 	fn try_from(s: &str) -> Result<Self, Self::Error> {
 		match s {
 			"json" => Ok(Format::Json),
+			"parquet" => Ok(Format::Parquet),
 			"peppi" => Ok(Format::Peppi),
 			"slippi" => Ok(Format::Slippi),
 			"null" => Ok(Format::Null),
@@ -56,12 +65,13 @@ fn parse_compression(s: &str) -> Result<Compression, String> {
 struct Opts {
 	debug_dir: Option<PathBuf>,
 	infile: Option<PathBuf>,
+	infiles: Vec<PathBuf>,
 	input_format: Option<Format>,
-	log_level: LevelFilter,
 	no_verify: bool,
 	outfile: Option<PathBuf>,
 	output_format: Format,
 	compression: Option<Compression>,
+	enum_names: bool,
 	short: bool,
 }
 
@@ -74,6 +84,15 @@ fn port_occupancy(game: &Game) -> Vec<PortOccupancy> {
 	).collect()
 }
 
+/// Apply the process-wide peppi serialization config. Must be called once,
+/// single-threaded, before any parallel fan-out (the setter is an unscoped
+/// global sink).
+fn apply_serialization_config(opts: &Opts) {
+	peppi::serde::set_serialization_config(peppi::serde::SerializationConfig {
+		enum_names: opts.enum_names,
+	});
+}
+
 fn write_json<W: Write>(game: Game, mut w: W) -> Result<(), Box<dyn Error>> {
 	let ports = port_occupancy(&game);
 	let frames = game.frames.into_struct_array(game.start.slippi.version, &ports).boxed();
@@ -95,6 +114,58 @@ fn write_json<W: Write>(game: Game, mut w: W) -> Result<(), Box<dyn Error>> {
 	Ok(())
 }
 
+fn parquet_compression(c: Option<Compression>) -> parquet_write::CompressionOptions {
+	use parquet_write::CompressionOptions;
+	match c {
+		Some(Compression::LZ4) => CompressionOptions::Lz4Raw,
+		Some(Compression::ZSTD) => CompressionOptions::Zstd(None),
+		_ => CompressionOptions::Uncompressed,
+	}
+}
+
+fn write_parquet<W: Write>(game: Game, w: W, opts: &Opts) -> Result<(), Box<dyn Error>> {
+	let ports = port_occupancy(&game);
+	let array: Box<dyn Array> =
+		game.frames.into_struct_array(game.start.slippi.version, &ports).boxed();
+	let schema = Schema::from(vec![
+		Field::new("frame", array.data_type().clone(), false),
+	]);
+
+	let options = parquet_write::WriteOptions {
+		write_statistics: true,
+		compression: parquet_compression(opts.compression),
+		version: parquet_write::Version::V2,
+		data_pagesize_limit: None,
+	};
+
+	let encodings: Vec<_> = schema.fields.iter()
+		.map(|f| parquet_write::transverse(&f.data_type, |_| parquet_write::Encoding::Plain))
+		.collect();
+
+	let chunks = vec![Ok(Chunk::new(vec![array]))];
+	let row_groups = parquet_write::RowGroupIterator::try_new(
+		chunks.into_iter(), &schema, options, encodings)?;
+
+	let mut writer = parquet_write::FileWriter::try_new(w, schema, options)?;
+	for group in row_groups {
+		writer.write(group?)?;
+	}
+
+	// Stash game context as file-level key-value metadata so a single
+	// `.parquet` is self-contained.
+	let mut kv = vec![
+		parquet_write::KeyValue::new("slippi.start".to_string(), serde_json::to_string(&game.start)?),
+	];
+	if let Some(end) = &game.end {
+		kv.push(parquet_write::KeyValue::new("slippi.end".to_string(), serde_json::to_string(end)?));
+	}
+	if let Some(meta) = &game.metadata {
+		kv.push(parquet_write::KeyValue::new("slippi.metadata".to_string(), serde_json::to_string(meta)?));
+	}
+	writer.end(Some(kv))?;
+	Ok(())
+}
+
 fn write_slippi<W: Write>(game: Game, w: &mut W) -> Result<(), Box<dyn Error>> {
 	slippi::write(w, &game)?;
 	w.flush()?;
@@ -110,6 +181,7 @@ fn write<W: Write>(game: Game, w: &mut W, opts: &Opts) -> Result<(), Box<dyn Err
 		}))?,
 		Slippi => write_slippi(game, w)?,
 		Json => write_json(game, w)?,
+		Parquet => write_parquet(game, w, opts)?,
 		Null => {},
 	}
 	Ok(())
@@ -127,6 +199,107 @@ fn convert(game: Game, opts: &Opts) -> Result<(), Box<dyn Error>> {
 	Ok(())
 }
 
+fn output_extension(format: Format) -> &'static str {
+	use Format::*;
+	match format {
+		Json => "json",
+		Parquet => "parquet",
+		Peppi | Slippi => "slp",
+		Null => "",
+	}
+}
+
+/// Recursively collect `.slp`/peppi replays under `root` into `out`.
+fn discover_replays(root: &Path, out: &mut Vec<PathBuf>) -> io::Result<()> {
+	if root.is_dir() {
+		let mut entries: Vec<_> = fs::read_dir(root)?
+			.collect::<Result<Vec<_>, _>>()?
+			.into_iter()
+			.map(|e| e.path())
+			.collect();
+		entries.sort();
+		for entry in entries {
+			discover_replays(&entry, out)?;
+		}
+	} else if root.extension().map(|e| e == "slp").unwrap_or(false) {
+		out.push(root.to_path_buf());
+	}
+	Ok(())
+}
+
+/// Map an input path to its output path under `outdir`, mirroring the input
+/// tree relative to `root`.
+fn output_path(root: &Path, src: &Path, outdir: &Path, format: Format) -> PathBuf {
+	let rel = src.strip_prefix(root).unwrap_or(src);
+	let mut dst = outdir.join(rel);
+	dst.set_extension(output_extension(format));
+	dst
+}
+
+fn convert_file(src: &Path, dst: &Path, opts: &Opts) -> Result<(), Box<dyn Error>> {
+	let (game, _) = read_game_(
+		File::open(src).map_err(|e| format!("couldn't open `{}`: {}", src.display(), e))?,
+		opts)?;
+	let hash = game.hash.clone();
+	match opts.output_format {
+		Format::Null => write(game, &mut io::sink(), opts)?,
+		_ => {
+			if let Some(parent) = dst.parent() {
+				fs::create_dir_all(parent)?;
+			}
+			write(game, &mut BufWriter::new(File::create(dst)?), opts)?;
+		},
+	}
+	// Round-trip verify each file, mirroring the single-file path in `_main`.
+	if no_verify_reason(opts).is_none() {
+		verify_peppi_at(hash.ok_or("missing hash")?, dst, opts)?;
+	}
+	Ok(())
+}
+
+/// Convert one or more files/directories to `--outfile`'s directory in
+/// parallel, isolating per-file errors and reporting a final summary.
+fn convert_batch(opts: &Opts) -> Result<(), Box<dyn Error>> {
+	let outdir = opts.outfile.as_ref()
+		.ok_or("`--outfile` (output directory) is required for batch conversion")?;
+
+	let mut jobs = Vec::new();
+	for root in &opts.infiles {
+		let mut files = Vec::new();
+		discover_replays(root, &mut files)?;
+		// For a file argument, mirror only its file name; for a directory,
+		// mirror the tree below it.
+		let base = if root.is_dir() { root.as_path() } else {
+			root.parent().unwrap_or(Path::new(""))
+		};
+		for src in files {
+			let dst = output_path(base, &src, outdir, opts.output_format);
+			jobs.push((src, dst));
+		}
+	}
+
+	info!("Converting {} replay(s)", jobs.len());
+	let now = std::time::Instant::now();
+	let failed: usize = jobs.par_iter().map(|(src, dst)| {
+		match convert_file(src, dst, opts) {
+			Ok(()) => 0,
+			Err(e) => {
+				error!("{}: {}", src.display(), e);
+				1
+			},
+		}
+	}).sum();
+
+	let succeeded = jobs.len() - failed;
+	info!("Converted {} file(s) ({} failed) in {} ms",
+		succeeded, failed, now.elapsed().as_millis());
+	if failed > 0 {
+		Err(format!("{} file(s) failed to convert", failed).into())
+	} else {
+		Ok(())
+	}
+}
+
 fn hash(f: &mut File) -> Result<String, Box<dyn Error>> {
 	let mut hasher = Box::new(Xxh3::new());
 	let mut buf = Vec::<u8>::new();
@@ -135,13 +308,99 @@ fn hash(f: &mut File) -> Result<String, Box<dyn Error>> {
 	Ok(peppi::io::format_hash(&hasher))
 }
 
+#[derive(Clone, Copy)]
+enum Algorithm {
+	Xxh3, Sha256
+}
+
+fn hash_file(path: &Path, algo: Algorithm) -> Result<String, Box<dyn Error>> {
+	let mut f = File::open(path)
+		.map_err(|e| format!("couldn't open `{}`: {}", path.display(), e))?;
+	match algo {
+		Algorithm::Xxh3 => hash(&mut f),
+		Algorithm::Sha256 => {
+			use sha2::{Digest, Sha256};
+			let mut hasher = Sha256::new();
+			io::copy(&mut f, &mut hasher)?;
+			Ok(format!("{:x}", hasher.finalize()))
+		},
+	}
+}
+
+fn check_manifests(manifests: &[PathBuf], algo: Algorithm) -> Result<(), Box<dyn Error>> {
+	let mut failed = 0usize;
+	for manifest in manifests {
+		let file = File::open(manifest)
+			.map_err(|e| format!("couldn't open `{}`: {}", manifest.display(), e))?;
+		for line in BufReader::new(file).lines() {
+			let line = line?;
+			let line = line.trim();
+			if line.is_empty() {
+				continue;
+			}
+			let (expected, path) = line.split_once("  ")
+				.ok_or_else(|| format!("malformed manifest line: {}", line))?;
+			match hash_file(Path::new(path), algo) {
+				Ok(ref actual) if actual == expected => println!("{}: OK", path),
+				Ok(_) => {
+					println!("{}: FAILED", path);
+					failed += 1;
+				},
+				Err(e) => {
+					println!("{}: FAILED ({})", path, e);
+					failed += 1;
+				},
+			}
+		}
+	}
+	if failed > 0 {
+		Err(format!("{} computed checksum(s) did NOT match", failed).into())
+	} else {
+		Ok(())
+	}
+}
+
+fn hash_main(matches: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+	let algo = match matches.get_one::<String>("algorithm").map(|s| s.as_str()) {
+		Some("sha256") => Algorithm::Sha256,
+		_ => Algorithm::Xxh3,
+	};
+	let files: Vec<PathBuf> = matches.get_many::<String>("files")
+		.map(|v| v.map(PathBuf::from).collect())
+		.unwrap_or_default();
+
+	if matches.get_flag("check") {
+		return check_manifests(&files, algo);
+	}
+
+	// Isolate per-file failures like `shasum`/`sha256sum`: log and keep going.
+	let mut failed = 0usize;
+	for path in &files {
+		match hash_file(path, algo) {
+			Ok(h) => println!("{}  {}", h, path.display()),
+			Err(e) => {
+				error!("{}: {}", path.display(), e);
+				failed += 1;
+			},
+		}
+	}
+	if failed > 0 {
+		Err(format!("{} file(s) could not be hashed", failed).into())
+	} else {
+		Ok(())
+	}
+}
+
 fn verify_peppi(hash_in: String, opts: &Opts) -> Result<(), Box<dyn Error>> {
+	verify_peppi_at(hash_in, opts.outfile.as_ref().unwrap(), opts)
+}
+
+fn verify_peppi_at(hash_in: String, outfile: &Path, opts: &Opts) -> Result<(), Box<dyn Error>> {
 	let now = std::time::Instant::now();
-	let outfile = opts.outfile.as_ref().unwrap();
 
 	let game = read_peppi(
 		&mut BufReader::new(
-			File::open(&outfile)
+			File::open(outfile)
 				.map_err(|e| format!("couldn't open `{}`: {}", outfile.display(), e))?),
 		opts)?;
 
@@ -232,37 +491,133 @@ fn read_game_<R: Read + Seek>(r: R, opts: &Opts) -> Result<(Game, Format), Box<d
 	Ok((game, format))
 }
 
-struct SkippingReader<R: Read> {
+/// Default size of the backward-seek window retained for a [`SeekingReader`].
+const SEEK_WINDOW: usize = 64 * 1024;
+
+/// A `Seek` shim over a non-seekable reader (e.g. STDIN) that tracks the true
+/// absolute offset and buffers recently-read bytes so the parser can rewind.
+///
+/// Forward seeks discard bytes from the underlying reader while advancing the
+/// offset counter; backward seeks within the retained window (`SEEK_WINDOW` by
+/// default) replay from the ring buffer, and seeks beyond it return an error
+/// rather than panicking. The reported position always equals the total bytes
+/// consumed from the underlying reader minus the bytes still replayable from
+/// the buffer.
+struct SeekingReader<R: Read> {
 	reader: R,
+	/// Current logical position (bytes delivered to the caller).
+	pos: u64,
+	/// Total bytes consumed from the underlying reader.
+	consumed: u64,
+	/// Ring buffer of recently-consumed bytes, ending at `consumed`.
+	buf: VecDeque<u8>,
+	/// Absolute offset of the first byte retained in `buf`.
+	buf_start: u64,
+	/// Maximum number of bytes to retain for backward seeks.
+	window: usize,
 }
 
-impl<R: Read> SkippingReader<R> {
+impl<R: Read> SeekingReader<R> {
 	fn new(reader: R) -> Self {
-		Self { reader }
+		Self::with_window(reader, SEEK_WINDOW)
+	}
+
+	fn with_window(reader: R, window: usize) -> Self {
+		Self {
+			reader,
+			pos: 0,
+			consumed: 0,
+			buf: VecDeque::new(),
+			buf_start: 0,
+			window,
+		}
+	}
+
+	/// Append freshly-consumed bytes to the ring buffer, evicting the oldest
+	/// bytes once the retained window is exceeded.
+	fn record(&mut self, bytes: &[u8]) {
+		self.buf.extend(bytes.iter().copied());
+		while self.buf.len() > self.window {
+			self.buf.pop_front();
+			self.buf_start += 1;
+		}
+	}
+
+	/// Discard bytes forward from the underlying reader until `consumed`
+	/// reaches `target`.
+	fn fill_forward(&mut self, target: u64) -> io::Result<()> {
+		let mut chunk = [0u8; 8192];
+		while self.consumed < target {
+			let want = (target - self.consumed).min(chunk.len() as u64) as usize;
+			let n = self.reader.read(&mut chunk[..want])?;
+			if n == 0 {
+				return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+					"seek past end of stream"));
+			}
+			self.record(&chunk[..n]);
+			self.consumed += n as u64;
+		}
+		Ok(())
 	}
 }
 
-impl<R: Read> Read for SkippingReader<R> {
-	fn read(&mut self, size: &mut [u8]) -> Result<usize, io::Error> {
-		self.reader.read(size)
+impl<R: Read> Read for SeekingReader<R> {
+	fn read(&mut self, dst: &mut [u8]) -> io::Result<usize> {
+		if dst.is_empty() {
+			return Ok(0);
+		}
+		// Replay buffered bytes first if the position is behind the reader.
+		if self.pos < self.consumed {
+			let start = (self.pos - self.buf_start) as usize;
+			let n = ((self.consumed - self.pos) as usize).min(dst.len());
+			for (i, b) in dst[..n].iter_mut().enumerate() {
+				*b = self.buf[start + i];
+			}
+			self.pos += n as u64;
+			return Ok(n);
+		}
+		let n = self.reader.read(dst)?;
+		self.record(&dst[..n]);
+		self.pos += n as u64;
+		self.consumed += n as u64;
+		Ok(n)
 	}
 }
 
-impl<R: Read> Seek for SkippingReader<R> {
-	fn seek(&mut self, pos: SeekFrom) -> Result<u64, io::Error> {
-		match pos {
-			SeekFrom::Current(offset) if offset >= 0 => {
-				io::copy(&mut self.reader.by_ref().take(offset as u64), &mut io::sink())?;
-				Ok(0) // we don't have a real position, so just return 0
+impl<R: Read> Seek for SeekingReader<R> {
+	fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+		let target = match pos {
+			SeekFrom::Start(p) => p,
+			SeekFrom::Current(n) => {
+				let t = self.pos as i64 + n;
+				if t < 0 {
+					return Err(io::Error::new(io::ErrorKind::InvalidInput,
+						"seek to a negative position"));
+				}
+				t as u64
 			},
-			_ => unimplemented!(),
+			SeekFrom::End(_) => return Err(io::Error::new(io::ErrorKind::Unsupported,
+				"seeking from the end of a non-seekable stream is not supported")),
+		};
+		if target > self.consumed {
+			self.fill_forward(target)?;
+		} else if target < self.buf_start {
+			return Err(io::Error::new(io::ErrorKind::Unsupported, format!(
+				"backward seek to {} is beyond the retained {}-byte window (earliest retained offset is {})",
+				target, self.window, self.buf_start)));
 		}
+		self.pos = target;
+		Ok(self.pos)
+	}
+
+	fn stream_position(&mut self) -> io::Result<u64> {
+		Ok(self.pos)
 	}
 }
 
 fn read_game(opts: &Opts) -> Result<(Game, Format), Box<dyn Error>> {
 	match &opts.infile {
-		None => read_game_(SkippingReader::new(io::stdin()), opts),
+		None => read_game_(SeekingReader::new(io::stdin()), opts),
 		Some(path) => read_game_(
 			File::open(path).map_err(|e| format!("couldn't open `{}`: {}", path.display(), e))?,
 			opts,
@@ -280,14 +635,33 @@ fn log_level(verbosity: u8) -> LevelFilter {
 	}
 }
 
-fn parse_opts() -> Opts {
-	let matches = Command::new("slp")
+fn cli() -> Command {
+	Command::new("slp")
 		.version(env!("CARGO_PKG_VERSION"))
 		.author("melkor <hohav@fastmail.com>")
 		.about("Inspector for Slippi SSBM replay files")
+		.subcommand(Command::new("hash")
+			.about("Print (or verify) hashes of the raw file bytes, `shasum`-style; files are not decoded, so a peppi-format file's hash is not its game's content hash")
+			.arg(Arg::new("files")
+				.help("Replay files to hash, or manifests to check with `--check`")
+				.index(1)
+				.num_args(0..))
+			.arg(Arg::new("algorithm")
+				.help("Hash algorithm")
+				.short('a')
+				.long("algorithm")
+				.num_args(1)
+				.value_parser(clap::builder::PossibleValuesParser::new(["xxh3", "sha256"]))
+				.default_value("xxh3"))
+			.arg(Arg::new("check")
+				.help("Read hashes from the given manifest(s) and report mismatches")
+				.short('c')
+				.long("check")
+				.action(ArgAction::SetTrue)))
 		.arg(Arg::new("game.slp")
-			.help("Replay file to parse (`-` for STDIN)")
-			.index(1))
+			.help("Replay file(s) or directory to parse (`-` for STDIN)")
+			.index(1)
+			.num_args(0..))
 		.arg(Arg::new("input-format")
 			.help("Input format")
 			.long("input-format")
@@ -303,7 +677,7 @@ fn parse_opts() -> Opts {
 			.short('f')
 			.long("format")
 			.num_args(1)
-			.value_parser(clap::builder::PossibleValuesParser::new(["json", "null", "peppi", "rust", "slippi"]))
+			.value_parser(clap::builder::PossibleValuesParser::new(["json", "null", "parquet", "peppi", "slippi"]))
 			.default_value("json"))
 		.arg(Arg::new("compression")
 			.help("Compression method")
@@ -311,6 +685,10 @@ fn parse_opts() -> Opts {
 			.long("compression")
 			.num_args(1)
 			.value_parser(clap::builder::PossibleValuesParser::new(["lz4", "zstd"])))
+		.arg(Arg::new("enum-names")
+			.help("Render enum fields as `14:WAIT` instead of bare integers (JSON only)")
+			.long("enum-names")
+			.action(ArgAction::SetTrue))
 		.arg(Arg::new("short")
 			.help("Don't output frame data")
 			.short('s')
@@ -329,32 +707,58 @@ fn parse_opts() -> Opts {
 			.short('v')
 			.long("verbose")
 			.action(ArgAction::Count))
-		.get_matches();
+}
 
+fn parse_opts(matches: &clap::ArgMatches) -> Opts {
 	Opts {
 		debug_dir: matches.get_one::<String>("debug-dir").map(PathBuf::from),
-		infile: matches.get_one::<String>("game.slp").map(|s| s.into()),
+		infile: None,
+		infiles: matches.get_many::<String>("game.slp")
+			.map(|v| v.map(PathBuf::from).collect())
+			.unwrap_or_default(),
 		input_format: matches.get_one::<String>("input-format").map(|f| (&f[..]).try_into().unwrap()),
-		log_level: log_level(*matches.get_one("verbose").unwrap()),
 		no_verify: matches.get_flag("no-verify"),
 		outfile: matches.get_one::<String>("outfile").map(|s| s.into()),
 		output_format: (&matches.get_one::<String>("format").unwrap()[..]).try_into().unwrap(),
 		compression: matches.get_one::<String>("compression").map(|c|
 			parse_compression(c).unwrap()
 		),
+		enum_names: matches.get_flag("enum-names"),
 		short: matches.get_flag("short"),
 	}
 }
 
 pub fn _main() -> Result<(), Box<dyn Error>> {
-	let mut opts = parse_opts();
+	let matches = cli().get_matches();
 
 	env_logger::builder()
-		.filter_level(opts.log_level)
+		.filter_level(log_level(matches.get_one::<u8>("verbose").copied().unwrap_or(0)))
 		.format_timestamp(None)
 		.format_target(false)
 		.init();
 
+	if let Some(hash_matches) = matches.subcommand_matches("hash") {
+		return hash_main(hash_matches);
+	}
+
+	let mut opts = parse_opts(&matches);
+
+	// Set the global serialization config once, before any parallel fan-out.
+	apply_serialization_config(&opts);
+
+	// A single directory, or more than one path, means batch mode: recurse
+	// into each, converting every replay to `--outfile`'s directory.
+	let batch = opts.infiles.len() > 1
+		|| opts.infiles.first().map(|p| p.is_dir()).unwrap_or(false);
+	if batch {
+		return convert_batch(&opts);
+	}
+
+	// Single-file mode: a lone path reads from that file, no path reads STDIN.
+	opts.infile = opts.infiles.first()
+		.filter(|p| p.as_os_str() != "-")
+		.cloned();
+
 	// don't check for "-", to allow the user to force reading from STDIN
 	// in case of TTY detection false-positives
 	if opts.infile.is_none() && atty::is(atty::Stream::Stdin) {
@@ -390,3 +794,81 @@ pub fn main() {
 		std::process::exit(2);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reads_sequentially_and_tracks_position() {
+		let data: Vec<u8> = (0 .. 32).collect();
+		let mut r = SeekingReader::with_window(&data[..], 64);
+		assert_eq!(r.stream_position().unwrap(), 0);
+		let mut buf = [0u8; 8];
+		r.read_exact(&mut buf).unwrap();
+		assert_eq!(&buf, &[0, 1, 2, 3, 4, 5, 6, 7]);
+		assert_eq!(r.stream_position().unwrap(), 8);
+	}
+
+	#[test]
+	fn forward_seek_discards_bytes() {
+		let data: Vec<u8> = (0 .. 32).collect();
+		let mut r = SeekingReader::with_window(&data[..], 64);
+		assert_eq!(r.seek(SeekFrom::Start(10)).unwrap(), 10);
+		let mut b = [0u8; 1];
+		r.read_exact(&mut b).unwrap();
+		assert_eq!(b[0], 10);
+	}
+
+	#[test]
+	fn backward_seek_within_window_replays() {
+		let data: Vec<u8> = (0 .. 32).collect();
+		let mut r = SeekingReader::with_window(&data[..], 64);
+		let mut buf = [0u8; 16];
+		r.read_exact(&mut buf).unwrap();
+		assert_eq!(r.seek(SeekFrom::Start(4)).unwrap(), 4);
+		let mut b = [0u8; 4];
+		r.read_exact(&mut b).unwrap();
+		assert_eq!(&b, &[4, 5, 6, 7]);
+		assert_eq!(r.seek(SeekFrom::Current(0)).unwrap(), 8);
+	}
+
+	#[test]
+	fn backward_seek_to_exact_window_boundary_ok() {
+		let data: Vec<u8> = (0 .. 32).collect();
+		// After reading 16 bytes with an 8-byte window, offsets 8..16 are retained.
+		let mut r = SeekingReader::with_window(&data[..], 8);
+		let mut buf = [0u8; 16];
+		r.read_exact(&mut buf).unwrap();
+		assert_eq!(r.seek(SeekFrom::Start(8)).unwrap(), 8);
+		let mut b = [0u8; 1];
+		r.read_exact(&mut b).unwrap();
+		assert_eq!(b[0], 8);
+	}
+
+	#[test]
+	fn backward_seek_beyond_window_errors() {
+		let data: Vec<u8> = (0 .. 32).collect();
+		let mut r = SeekingReader::with_window(&data[..], 8);
+		let mut buf = [0u8; 16];
+		r.read_exact(&mut buf).unwrap();
+		let err = r.seek(SeekFrom::Start(4)).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+	}
+
+	#[test]
+	fn negative_current_seek_rejected() {
+		let data: Vec<u8> = (0 .. 8).collect();
+		let mut r = SeekingReader::with_window(&data[..], 64);
+		let err = r.seek(SeekFrom::Current(-1)).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn seek_past_eof_errors() {
+		let data: Vec<u8> = (0 .. 8).collect();
+		let mut r = SeekingReader::with_window(&data[..], 64);
+		let err = r.seek(SeekFrom::Start(100)).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+}
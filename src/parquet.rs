@@ -13,22 +13,137 @@ use parquet::{
 	column::writer::{get_typed_column_writer_mut},
 	data_type::{BoolType, FloatType, Int32Type, Int64Type},
 	file::{
-		properties::WriterProperties,
+		properties::{EnabledStatistics, WriterProperties},
 		writer::{FileWriter, RowGroupWriter, SerializedFileWriter},
 	},
+	format::KeyValue,
 	schema::{
 		parser::parse_message_type,
+		types::ColumnPath,
 	},
 };
 
 use peppi::{
-	game::FIRST_FRAME_INDEX,
+	game::{FIRST_FRAME_INDEX, immutable::Game},
 	frame::{PreCol, PostCol, ItemCol},
 	primitives::Direction,
 };
 
 use super::transform;
 
+/// Tunables for the Parquet writer.
+///
+/// The defaults are no compression, `PLAIN` encoding, and dictionary,
+/// statistics and bloom filters all disabled (the `index` column is always
+/// `DELTA_BINARY_PACKED`, whatever the options); callers that
+/// care about file size or query pushdown can pick a compression codec,
+/// enable dictionary encoding, select an alternate column encoding, or turn
+/// on statistics/bloom filters.
+pub struct WriteOptions {
+	pub compression: Compression,
+	pub encoding: Encoding,
+	pub dictionary: bool,
+	/// Emit min/max/null-count statistics to enable row-group pruning.
+	pub statistics: bool,
+	/// Build bloom filters on the categorical integer columns so query
+	/// engines can skip row groups for equality predicates.
+	pub bloom_filters: bool,
+	/// Target false-positive probability for the bloom filters.
+	pub bloom_filter_fpp: f64,
+}
+
+impl Default for WriteOptions {
+	fn default() -> Self {
+		Self {
+			compression: Compression::UNCOMPRESSED,
+			encoding: Encoding::PLAIN,
+			dictionary: false,
+			statistics: false,
+			bloom_filters: false,
+			bloom_filter_fpp: 0.05,
+		}
+	}
+}
+
+/// Per-column encoding plan for a schema: which columns get bloom filters,
+/// delta-packed integer encoding (monotonic columns), and dictionary/RLE
+/// encoding (constant or low-cardinality columns).
+struct ColumnEncoding {
+	bloom: &'static [&'static str],
+	delta: &'static [&'static str],
+	dict: &'static [&'static str],
+}
+
+/// The frame index is strictly increasing, so it delta-packs to a few bytes
+/// per block; port/is_follower are constant per row group and the categorical
+/// columns are low-cardinality, so they dictionary-encode to near-nothing.
+const FRAME_COLUMNS: ColumnEncoding = ColumnEncoding {
+	bloom: &["pre.state", "post.state", "post.character"],
+	delta: &["index"],
+	dict: &["game_id", "port", "is_follower",
+		"pre.direction", "pre.state", "post.direction", "post.state", "post.character"],
+};
+
+const ITEM_COLUMNS: ColumnEncoding = ColumnEncoding {
+	bloom: &["type", "id", "state", "owner"],
+	delta: &["index"],
+	dict: &["game_id", "type", "state", "direction", "owner"],
+};
+
+/// Serialize the game's start/metadata blocks as JSON and attach them to the
+/// file footer so the Parquet is self-describing without the original `.slp`.
+fn append_game_metadata<W: std::io::Write + Send>(writer: &mut SerializedFileWriter<W>, game: &Game) -> Result<(), Box<dyn Error>> {
+	writer.append_key_value_metadata(KeyValue {
+		key: "slippi.start".to_string(),
+		value: Some(serde_json::to_string(&game.start)?),
+	});
+	if let Some(metadata) = &game.metadata {
+		writer.append_key_value_metadata(KeyValue {
+			key: "slippi.metadata".to_string(),
+			value: Some(serde_json::to_string(metadata)?),
+		});
+	}
+	Ok(())
+}
+
+fn writer_properties(opts: &WriteOptions, columns: &ColumnEncoding) -> WriterProperties {
+	let mut builder = WriterProperties::builder()
+		.set_writer_version(parquet::file::properties::WriterVersion::PARQUET_2_0)
+		.set_dictionary_enabled(opts.dictionary)
+		.set_encoding(opts.encoding)
+		.set_compression(opts.compression)
+		.set_statistics_enabled(if opts.statistics {
+			EnabledStatistics::Chunk
+		} else {
+			EnabledStatistics::None
+		});
+	// Column names are dotted paths ("post.state"); split them so they address
+	// the nested leaf columns rather than a single top-level name.
+	let path_of = |col: &str| ColumnPath::new(col.split('.').map(String::from).collect());
+	if opts.bloom_filters {
+		for col in columns.bloom {
+			let path = path_of(*col);
+			builder = builder
+				.set_column_bloom_filter_enabled(path.clone(), true)
+				.set_column_bloom_filter_fpp(path, opts.bloom_filter_fpp);
+		}
+	}
+	// Monotonic columns delta-pack; a per-column encoding and dictionary must
+	// not both be set, so disable the dictionary on those paths explicitly.
+	for col in columns.delta {
+		let path = path_of(*col);
+		builder = builder
+			.set_column_dictionary_enabled(path.clone(), false)
+			.set_column_encoding(path, Encoding::DELTA_BINARY_PACKED);
+	}
+	if opts.dictionary {
+		for col in columns.dict {
+			builder = builder.set_column_dictionary_enabled(path_of(*col), true);
+		}
+	}
+	builder.build()
+}
+
 const SCHEMA_FRAME_PRE: &str = "
 required group position {
 	required float x;
@@ -174,7 +289,11 @@ fn write_f32(rgw: &mut Box<dyn RowGroupWriter>, data: &[f32]) -> Result<(), Box<
 	Ok(())
 }
 
-fn write_pre(rgw: &mut Box<dyn RowGroupWriter>, pre: &PreCol, p: usize) -> Result<(), Box<dyn Error>> {
+/// Writes the fields common to every Slippi version, in declared schema
+/// order. Shared by the required-schema (`write_pre`) and optional-schema
+/// (`dataset_write_pre`) writers so the column order can't diverge between
+/// them again.
+fn write_pre_base(rgw: &mut Box<dyn RowGroupWriter>, pre: &PreCol, p: usize) -> Result<(), Box<dyn Error>> {
 	write_f32(rgw, &pre.position[p].iter().map(|p| p.x).collect::<Vec<_>>())?;
 	write_f32(rgw, &pre.position[p].iter().map(|p| p.y).collect::<Vec<_>>())?;
 	write_bool(rgw, &pre.direction[p].iter().map(|d| *d == Direction::Right).collect::<Vec<_>>())?;
@@ -182,13 +301,18 @@ fn write_pre(rgw: &mut Box<dyn RowGroupWriter>, pre: &PreCol, p: usize) -> Resul
 	write_f32(rgw, &pre.joystick[p].iter().map(|p| p.y).collect::<Vec<_>>())?;
 	write_f32(rgw, &pre.cstick[p].iter().map(|p| p.x).collect::<Vec<_>>())?;
 	write_f32(rgw, &pre.cstick[p].iter().map(|p| p.y).collect::<Vec<_>>())?;
-	write_f32(rgw, &pre.triggers[p].iter().map(|t| t.logical).collect::<Vec<_>>())?;
 	write_f32(rgw, &pre.triggers[p].iter().map(|t| t.physical.l).collect::<Vec<_>>())?;
 	write_f32(rgw, &pre.triggers[p].iter().map(|t| t.physical.r).collect::<Vec<_>>())?;
+	write_f32(rgw, &pre.triggers[p].iter().map(|t| t.logical).collect::<Vec<_>>())?;
 	write_i32(rgw, &pre.random_seed[p].iter().map(|r| *r as i32).collect::<Vec<_>>())?;
-	write_i32(rgw, &pre.buttons[p].iter().map(|b| b.logical.0 as i32).collect::<Vec<_>>())?;
 	write_i32(rgw, &pre.buttons[p].iter().map(|b| b.physical.0 as i32).collect::<Vec<_>>())?;
+	write_i32(rgw, &pre.buttons[p].iter().map(|b| b.logical.0 as i32).collect::<Vec<_>>())?;
 	write_i32(rgw, &pre.state[p].iter().map(|s| u16::from(*s) as i32).collect::<Vec<_>>())?;
+	Ok(())
+}
+
+fn write_pre(rgw: &mut Box<dyn RowGroupWriter>, pre: &PreCol, p: usize) -> Result<(), Box<dyn Error>> {
+	write_pre_base(rgw, pre, p)?;
 
 	// v1.2
 	if let Some(raw_analog_x) = &pre.raw_analog_x {
@@ -202,7 +326,11 @@ fn write_pre(rgw: &mut Box<dyn RowGroupWriter>, pre: &PreCol, p: usize) -> Resul
 	Ok(())
 }
 
-fn write_post(rgw: &mut Box<dyn RowGroupWriter>, post: &PostCol, p: usize) -> Result<(), Box<dyn Error>> {
+/// Writes the fields common to every Slippi version, in declared schema
+/// order. Shared by the required-schema (`write_post`) and optional-schema
+/// (`dataset_write_post`) writers so the column order can't diverge between
+/// them again.
+fn write_post_base(rgw: &mut Box<dyn RowGroupWriter>, post: &PostCol, p: usize) -> Result<(), Box<dyn Error>> {
 	write_f32(rgw, &post.position[p].iter().map(|p| p.x).collect::<Vec<_>>())?;
 	write_f32(rgw, &post.position[p].iter().map(|p| p.y).collect::<Vec<_>>())?;
 	write_bool(rgw, &post.direction[p].iter().map(|d| *d == Direction::Right).collect::<Vec<_>>())?;
@@ -214,6 +342,11 @@ fn write_post(rgw: &mut Box<dyn RowGroupWriter>, post: &PostCol, p: usize) -> Re
 	write_i32(rgw, &post.combo_count[p].iter().map(|c| *c as i32).collect::<Vec<_>>())?;
 	write_i32(rgw, &post.last_hit_by[p].iter().map(|l| l.map(|l| l as i32).unwrap_or(u8::MAX as i32)).collect::<Vec<_>>())?;
 	write_i32(rgw, &post.stocks[p].iter().map(|s| *s as i32).collect::<Vec<_>>())?;
+	Ok(())
+}
+
+fn write_post(rgw: &mut Box<dyn RowGroupWriter>, post: &PostCol, p: usize) -> Result<(), Box<dyn Error>> {
+	write_post_base(rgw, post, p)?;
 
 	// v0.2
 	if let Some(state_age) = &post.state_age {
@@ -251,7 +384,11 @@ fn write_post(rgw: &mut Box<dyn RowGroupWriter>, post: &PostCol, p: usize) -> Re
 	Ok(())
 }
 
-fn write_item(rgw: &mut Box<dyn RowGroupWriter>, item: &ItemCol) -> Result<(), Box<dyn Error>> {
+/// Writes the fields common to every Slippi version, in declared schema
+/// order. Shared by the required-schema (`write_item`) and optional-schema
+/// (`dataset_write_item`) writers so the column order can't diverge between
+/// them again.
+fn write_item_base(rgw: &mut Box<dyn RowGroupWriter>, item: &ItemCol) -> Result<(), Box<dyn Error>> {
 	write_i32(rgw, &item.index)?;
 	write_i32(rgw, &item.id.iter().map(|i| *i as i32).collect::<Vec<_>>())?;
 	write_i32(rgw, &item.r#type.iter().map(|t| t.0 as i32).collect::<Vec<_>>())?;
@@ -263,6 +400,11 @@ fn write_item(rgw: &mut Box<dyn RowGroupWriter>, item: &ItemCol) -> Result<(), B
 	write_f32(rgw, &item.velocity.iter().map(|v| v.y).collect::<Vec<_>>())?;
 	write_i32(rgw, &item.damage.iter().map(|d| *d as i32).collect::<Vec<_>>())?;
 	write_f32(rgw, &item.timer)?;
+	Ok(())
+}
+
+fn write_item(rgw: &mut Box<dyn RowGroupWriter>, item: &ItemCol) -> Result<(), Box<dyn Error>> {
+	write_item_base(rgw, item)?;
 
 	// v3.2
 	if let Some(misc) = &item.misc {
@@ -343,14 +485,13 @@ message item_data {{
 		schema_item(item))
 }
 
-pub fn write_frames<P: AsRef<Path>>(frames: &transform::Frames, path: P) -> Result<(), Box<dyn Error>> {
+pub fn write_frames<P: AsRef<Path>>(game: &Game, frames: &transform::Frames, path: P) -> Result<(), Box<dyn Error>> {
+	write_frames_with(game, frames, path, &WriteOptions::default())
+}
+
+pub fn write_frames_with<P: AsRef<Path>>(game: &Game, frames: &transform::Frames, path: P, opts: &WriteOptions) -> Result<(), Box<dyn Error>> {
 	let schema = Arc::new(parse_message_type(&schema_frames(frames))?);
-	let props = Arc::new(WriterProperties::builder()
-		.set_writer_version(parquet::file::properties::WriterVersion::PARQUET_2_0)
-		.set_dictionary_enabled(false)
-		.set_encoding(Encoding::PLAIN)
-		.set_compression(Compression::UNCOMPRESSED)
-		.build());
+	let props = Arc::new(writer_properties(opts, &FRAME_COLUMNS));
 	let file = File::create(path)?;
 	let mut writer = SerializedFileWriter::new(file, schema, props)?;
 
@@ -386,18 +527,18 @@ pub fn write_frames<P: AsRef<Path>>(frames: &transform::Frames, path: P) -> Resu
 		}
 	}
 
+	append_game_metadata(&mut writer, game)?;
 	writer.close()?;
 	Ok(())
 }
 
-pub fn write_items<P: AsRef<Path>>(item: &ItemCol, path: P) -> Result<(), Box<dyn Error>> {
+pub fn write_items<P: AsRef<Path>>(game: &Game, item: &ItemCol, path: P) -> Result<(), Box<dyn Error>> {
+	write_items_with(game, item, path, &WriteOptions::default())
+}
+
+pub fn write_items_with<P: AsRef<Path>>(game: &Game, item: &ItemCol, path: P, opts: &WriteOptions) -> Result<(), Box<dyn Error>> {
 	let schema = Arc::new(parse_message_type(&schema_items(item))?);
-	let props = Arc::new(WriterProperties::builder()
-		.set_writer_version(parquet::file::properties::WriterVersion::PARQUET_2_0)
-		.set_dictionary_enabled(false)
-		.set_encoding(Encoding::PLAIN)
-		.set_compression(Compression::UNCOMPRESSED)
-		.build());
+	let props = Arc::new(writer_properties(opts, &ITEM_COLUMNS));
 	let file = File::create(path)?;
 	let mut writer = SerializedFileWriter::new(file, schema, props)?;
 
@@ -405,7 +546,287 @@ pub fn write_items<P: AsRef<Path>>(item: &ItemCol, path: P) -> Result<(), Box<dy
 	write_item(&mut rgw, item)?;
 	writer.close_row_group(rgw)?;
 
+	append_game_metadata(&mut writer, game)?;
 	writer.close()?;
 
 	Ok(())
 }
+
+// --- Dataset writer ---------------------------------------------------------
+//
+// A single-file writer can only hold one game, since its schema is fixed to
+// that game's Slippi version. For dataset-scale analytics we instead write to
+// the *union* schema: every versioned block is `optional` and games that lack
+// it contribute nulls. A leading `game_id` column ties each row back to its
+// source replay.
+
+const DS_FRAME_PRE_V1_2: &str = "optional int32 raw_analog_x (UINT_8);";
+const DS_FRAME_PRE_V1_4: &str = "optional float damage;";
+const DS_FRAME_POST_V0_2: &str = "optional float state_age;";
+const DS_FRAME_POST_V2_0: &str = "
+optional int64 flags (UINT_64);
+optional float misc_as;
+optional boolean airborne;
+optional int32 ground (UINT_16);
+optional int32 jumps (UINT_8);
+optional int32 l_cancel (UINT_8);
+";
+const DS_FRAME_POST_V2_1: &str = "optional int32 hurtbox_state (UINT_8);";
+// The group is optional but its leaves stay required, so every velocity column
+// shares a single (0/1) definition level.
+const DS_FRAME_POST_V3_5: &str = "
+optional group velocities {
+	required group autogenous {
+		required float x;
+		required float y;
+	}
+	required group knockback {
+		required float x;
+		required float y;
+	}
+}
+";
+const DS_FRAME_POST_V3_8: &str = "optional float hitlag;";
+const DS_ITEM_V3_2: &str = "optional int32 misc (UINT_32);";
+const DS_ITEM_V3_6: &str = "optional int32 owner (UINT_8);";
+
+fn dataset_schema_frames() -> String {
+	format!("
+message frame_data {{
+	required int64 game_id;
+	required int32 index;
+	required int32 port (UINT_8);
+	required boolean is_follower;
+	required group pre {{ {}{}{} }}
+	required group post {{ {}{}{}{}{}{} }}
+}}",
+		SCHEMA_FRAME_PRE, DS_FRAME_PRE_V1_2, DS_FRAME_PRE_V1_4,
+		SCHEMA_FRAME_POST, DS_FRAME_POST_V0_2, DS_FRAME_POST_V2_0,
+		DS_FRAME_POST_V2_1, DS_FRAME_POST_V3_5, DS_FRAME_POST_V3_8)
+}
+
+fn dataset_schema_items() -> String {
+	format!("
+message item_data {{
+	required int64 game_id;
+	{}{}{}
+}}",
+		SCHEMA_ITEM, DS_ITEM_V3_2, DS_ITEM_V3_6)
+}
+
+fn write_opt_bool(rgw: &mut Box<dyn RowGroupWriter>, data: Option<&[bool]>, n: usize) -> Result<(), Box<dyn Error>> {
+	let mut c = rgw.next_column()?.ok_or("no column")?;
+	let w = get_typed_column_writer_mut::<BoolType>(&mut c);
+	match data {
+		Some(d) => w.write_batch(d, Some(&vec![1i16; d.len()]), None)?,
+		None => w.write_batch(&[], Some(&vec![0i16; n]), None)?,
+	};
+	rgw.close_column(c)?;
+	Ok(())
+}
+
+fn write_opt_i32(rgw: &mut Box<dyn RowGroupWriter>, data: Option<&[i32]>, n: usize) -> Result<(), Box<dyn Error>> {
+	let mut c = rgw.next_column()?.ok_or("no column")?;
+	let w = get_typed_column_writer_mut::<Int32Type>(&mut c);
+	match data {
+		Some(d) => w.write_batch(d, Some(&vec![1i16; d.len()]), None)?,
+		None => w.write_batch(&[], Some(&vec![0i16; n]), None)?,
+	};
+	rgw.close_column(c)?;
+	Ok(())
+}
+
+fn write_opt_i64(rgw: &mut Box<dyn RowGroupWriter>, data: Option<&[i64]>, n: usize) -> Result<(), Box<dyn Error>> {
+	let mut c = rgw.next_column()?.ok_or("no column")?;
+	let w = get_typed_column_writer_mut::<Int64Type>(&mut c);
+	match data {
+		Some(d) => w.write_batch(d, Some(&vec![1i16; d.len()]), None)?,
+		None => w.write_batch(&[], Some(&vec![0i16; n]), None)?,
+	};
+	rgw.close_column(c)?;
+	Ok(())
+}
+
+fn write_opt_f32(rgw: &mut Box<dyn RowGroupWriter>, data: Option<&[f32]>, n: usize) -> Result<(), Box<dyn Error>> {
+	let mut c = rgw.next_column()?.ok_or("no column")?;
+	let w = get_typed_column_writer_mut::<FloatType>(&mut c);
+	match data {
+		Some(d) => w.write_batch(d, Some(&vec![1i16; d.len()]), None)?,
+		None => w.write_batch(&[], Some(&vec![0i16; n]), None)?,
+	};
+	rgw.close_column(c)?;
+	Ok(())
+}
+
+fn dataset_write_pre(rgw: &mut Box<dyn RowGroupWriter>, pre: &PreCol, p: usize, n: usize) -> Result<(), Box<dyn Error>> {
+	write_pre_base(rgw, pre, p)?;
+
+	let raw_analog_x = pre.raw_analog_x.as_ref()
+		.map(|r| r[p].iter().map(|r| *r as i32).collect::<Vec<_>>());
+	write_opt_i32(rgw, raw_analog_x.as_deref(), n)?;
+	write_opt_f32(rgw, pre.damage.as_ref().map(|d| d[p].as_slice()), n)?;
+
+	Ok(())
+}
+
+fn dataset_write_post(rgw: &mut Box<dyn RowGroupWriter>, post: &PostCol, p: usize, n: usize) -> Result<(), Box<dyn Error>> {
+	write_post_base(rgw, post, p)?;
+
+	write_opt_f32(rgw, post.state_age.as_ref().map(|s| s[p].as_slice()), n)?;
+
+	let flags = post.flags.as_ref().map(|f| f[p].iter().map(|f| f.0 as i64).collect::<Vec<_>>());
+	write_opt_i64(rgw, flags.as_deref(), n)?;
+	write_opt_f32(rgw, post.misc_as.as_ref().map(|m| m[p].as_slice()), n)?;
+	write_opt_bool(rgw, post.airborne.as_ref().map(|a| a[p].as_slice()), n)?;
+	let ground = post.ground.as_ref().map(|g| g[p].iter().map(|g| *g as i32).collect::<Vec<_>>());
+	write_opt_i32(rgw, ground.as_deref(), n)?;
+	let jumps = post.jumps.as_ref().map(|j| j[p].iter().map(|j| *j as i32).collect::<Vec<_>>());
+	write_opt_i32(rgw, jumps.as_deref(), n)?;
+	let l_cancel = post.l_cancel.as_ref().map(|l| l[p].iter().map(|l| match l {
+		None => 0,
+		Some(true) => 1,
+		Some(false) => 2,
+	}).collect::<Vec<_>>());
+	write_opt_i32(rgw, l_cancel.as_deref(), n)?;
+
+	let hurtbox_state = post.hurtbox_state.as_ref().map(|h| h[p].iter().map(|h| h.0 as i32).collect::<Vec<_>>());
+	write_opt_i32(rgw, hurtbox_state.as_deref(), n)?;
+
+	let vel_auto_x = post.velocities.as_ref().map(|v| v[p].iter().map(|v| v.autogenous.x).collect::<Vec<_>>());
+	write_opt_f32(rgw, vel_auto_x.as_deref(), n)?;
+	let vel_auto_y = post.velocities.as_ref().map(|v| v[p].iter().map(|v| v.autogenous.y).collect::<Vec<_>>());
+	write_opt_f32(rgw, vel_auto_y.as_deref(), n)?;
+	let vel_kb_x = post.velocities.as_ref().map(|v| v[p].iter().map(|v| v.knockback.x).collect::<Vec<_>>());
+	write_opt_f32(rgw, vel_kb_x.as_deref(), n)?;
+	let vel_kb_y = post.velocities.as_ref().map(|v| v[p].iter().map(|v| v.knockback.y).collect::<Vec<_>>());
+	write_opt_f32(rgw, vel_kb_y.as_deref(), n)?;
+
+	write_opt_f32(rgw, post.hitlag.as_ref().map(|h| h[p].as_slice()), n)?;
+
+	Ok(())
+}
+
+fn dataset_write_item(rgw: &mut Box<dyn RowGroupWriter>, item: &ItemCol) -> Result<(), Box<dyn Error>> {
+	let n = item.index.len();
+	write_item_base(rgw, item)?;
+
+	let misc = item.misc.as_ref().map(|m| m.iter().map(|m| u32::from_le_bytes(*m) as i32).collect::<Vec<_>>());
+	write_opt_i32(rgw, misc.as_deref(), n)?;
+	let owner = item.owner.as_ref().map(|o| o.iter()
+		.map(|o| o.map(|o| o as i32).unwrap_or(u8::MAX as i32))
+		.collect::<Vec<_>>());
+	write_opt_i32(rgw, owner.as_deref(), n)?;
+
+	Ok(())
+}
+
+/// Accumulates many games into one pair of frame/item Parquet files sharing a
+/// single union schema, with a leading `game_id` column and one row group per
+/// game (or per port, for frames).
+pub struct DatasetWriter {
+	frames: SerializedFileWriter<File>,
+	items: SerializedFileWriter<File>,
+}
+
+impl DatasetWriter {
+	pub fn new<P: AsRef<Path>>(frames_path: P, items_path: P) -> Result<Self, Box<dyn Error>> {
+		Self::with_options(frames_path, items_path, &WriteOptions::default())
+	}
+
+	pub fn with_options<P: AsRef<Path>>(frames_path: P, items_path: P, opts: &WriteOptions) -> Result<Self, Box<dyn Error>> {
+		let frame_schema = Arc::new(parse_message_type(&dataset_schema_frames())?);
+		let item_schema = Arc::new(parse_message_type(&dataset_schema_items())?);
+		Ok(Self {
+			frames: SerializedFileWriter::new(
+				File::create(frames_path)?, frame_schema,
+				Arc::new(writer_properties(opts, &FRAME_COLUMNS)))?,
+			items: SerializedFileWriter::new(
+				File::create(items_path)?, item_schema,
+				Arc::new(writer_properties(opts, &ITEM_COLUMNS)))?,
+		})
+	}
+
+	pub fn append_game(&mut self, game_id: i64, frames: &transform::Frames) -> Result<(), Box<dyn Error>> {
+		let num_ports = frames.leader.pre.state.len();
+		let num_frames = frames.leader.pre.state[0].len();
+
+		let frame_indexes: Vec<_> = (0 .. num_frames)
+			.map(|idx| idx as i32 + FIRST_FRAME_INDEX).collect();
+		let game_ids = vec![game_id; num_frames];
+
+		for port in 0 .. num_ports {
+			let mut rgw = self.frames.next_row_group()?;
+			write_i64(&mut rgw, &game_ids)?;
+			write_i32(&mut rgw, &frame_indexes)?;
+			write_i32(&mut rgw, &vec![port as i32; num_frames])?;
+			write_bool(&mut rgw, &vec![false; num_frames])?;
+			dataset_write_pre(&mut rgw, &frames.leader.pre, port, num_frames)?;
+			dataset_write_post(&mut rgw, &frames.leader.post, port, num_frames)?;
+			self.frames.close_row_group(rgw)?;
+		}
+
+		for port in 0 .. num_ports {
+			use peppi::character::Internal;
+			match frames.leader.post.character[port][0] {
+				Internal::POPO | Internal::NANA => {
+					let mut rgw = self.frames.next_row_group()?;
+					write_i64(&mut rgw, &game_ids)?;
+					write_i32(&mut rgw, &frame_indexes)?;
+					write_i32(&mut rgw, &vec![port as i32; num_frames])?;
+					write_bool(&mut rgw, &vec![true; num_frames])?;
+					dataset_write_pre(&mut rgw, &frames.follower.pre, port, num_frames)?;
+					dataset_write_post(&mut rgw, &frames.follower.post, port, num_frames)?;
+					self.frames.close_row_group(rgw)?;
+				},
+				_ => {},
+			}
+		}
+
+		Ok(())
+	}
+
+	pub fn append_items(&mut self, game_id: i64, item: &ItemCol) -> Result<(), Box<dyn Error>> {
+		let mut rgw = self.items.next_row_group()?;
+		write_i64(&mut rgw, &vec![game_id; item.index.len()])?;
+		dataset_write_item(&mut rgw, item)?;
+		self.items.close_row_group(rgw)?;
+		Ok(())
+	}
+
+	pub fn close(self) -> Result<(), Box<dyn Error>> {
+		self.frames.close()?;
+		self.items.close()?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn writer_properties_resolves_nested_dotted_paths() {
+		let opts = WriteOptions { dictionary: true, bloom_filters: true, ..WriteOptions::default() };
+		let props = writer_properties(&opts, &FRAME_COLUMNS);
+		let pre_state = ColumnPath::new(vec!["pre".to_string(), "state".to_string()]);
+		let post_character = ColumnPath::new(vec!["post".to_string(), "character".to_string()]);
+
+		assert!(props.dictionary_enabled(&pre_state));
+		assert!(props.dictionary_enabled(&post_character));
+		assert!(props.dictionary_enabled(&ColumnPath::from("port")));
+		assert!(props.bloom_filter_properties(&pre_state).is_some());
+		assert!(props.bloom_filter_properties(&post_character).is_some());
+
+		assert_eq!(props.encoding(&ColumnPath::from("index")), Some(Encoding::DELTA_BINARY_PACKED));
+		assert!(!props.dictionary_enabled(&ColumnPath::from("index")));
+	}
+
+	#[test]
+	fn writer_properties_dictionary_opt_out_skips_categorical_columns() {
+		let opts = WriteOptions { dictionary: false, ..WriteOptions::default() };
+		let props = writer_properties(&opts, &FRAME_COLUMNS);
+
+		assert!(!props.dictionary_enabled(&ColumnPath::new(vec!["pre".to_string(), "state".to_string()])));
+		assert!(!props.dictionary_enabled(&ColumnPath::new(vec!["post".to_string(), "character".to_string()])));
+	}
+}